@@ -0,0 +1,206 @@
+//! Grouping [`Record`]s into checklists (eBird sampling events).
+//!
+//! The EBD is one row per species, but the natural analytical unit is a
+//! *checklist*: all species reported from one `SAMPLING EVENT IDENTIFIER`,
+//! sharing locality, date, observer, and effort metadata.
+
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+};
+
+use crate::{OwnedRecord, Reader, Record};
+
+/// All species observations from one eBird sampling event, as grouped by
+/// [`ChecklistReader`] or [`group_checklists_buffered`].
+#[derive(Debug)]
+pub struct Checklist {
+    observations: Vec<OwnedRecord>,
+}
+
+impl Checklist {
+    /// The record for the first species reported on this checklist. Every
+    /// field shared across the checklist (locality, date, observer, effort,
+    /// ...) can be read from it; only the species-specific fields differ
+    /// between [`Checklist::observations`].
+    pub fn metadata(&self) -> &Record<'_> {
+        self.observations[0].get()
+    }
+
+    /// The checklist's `SAMPLING EVENT IDENTIFIER`.
+    pub fn sampling_event_identifier(&self) -> &str {
+        self.metadata().sampling_event_identifier
+    }
+
+    /// The checklist's `GROUP IDENTIFIER`, shared by every checklist
+    /// submitted as part of the same group outing, or `None` if it was not
+    /// part of a group.
+    pub fn group_identifier(&self) -> Option<&str> {
+        let id = self.metadata().group_identifier;
+        (!id.is_empty()).then_some(id)
+    }
+
+    /// Every per-species observation reported on this checklist.
+    pub fn observations(&self) -> &[OwnedRecord] {
+        &self.observations
+    }
+}
+
+/// A streaming adapter over [`Reader`] that groups consecutive rows sharing
+/// a `SAMPLING EVENT IDENTIFIER` into a [`Checklist`].
+///
+/// This assumes the underlying EBD file is sorted by sampling event, which
+/// is the common case for exports; use [`group_checklists_buffered`] if the
+/// input may be in arbitrary order.
+pub struct ChecklistReader<'a, R: io::Read> {
+    reader: Reader<'a, R>,
+    pending: Option<OwnedRecord>,
+}
+
+impl<'a, R: io::Read> ChecklistReader<'a, R> {
+    pub fn new(reader: Reader<'a, R>) -> Self {
+        ChecklistReader {
+            reader,
+            pending: None,
+        }
+    }
+
+    /// Reads the next checklist, or `None` at end of input.
+    pub fn read_checklist(&mut self) -> csv::Result<Option<Checklist>> {
+        let first = match self.pending.take() {
+            Some(record) => record,
+            None => {
+                if !self.reader.advance()? {
+                    return Ok(None);
+                }
+                self.reader.read_record_owned()?
+            }
+        };
+
+        let event_id = first.get().sampling_event_identifier.to_owned();
+        let mut observations = vec![first];
+
+        while self.reader.advance()? {
+            let record = self.reader.read_record_owned()?;
+            if record.get().sampling_event_identifier == event_id {
+                observations.push(record);
+            } else {
+                self.pending = Some(record);
+                break;
+            }
+        }
+
+        Ok(Some(Checklist { observations }))
+    }
+}
+
+/// Groups every record from `reader` into [`Checklist`]s keyed by
+/// `SAMPLING EVENT IDENTIFIER`, buffering the whole input in memory.
+///
+/// Unlike [`ChecklistReader`], this does not assume the input is sorted by
+/// sampling event.
+pub fn group_checklists_buffered<R: io::Read>(
+    reader: &mut Reader<'_, R>,
+) -> csv::Result<Vec<Checklist>> {
+    let mut by_event: HashMap<String, usize> = HashMap::new();
+    let mut checklists: Vec<Vec<OwnedRecord>> = Vec::new();
+
+    while reader.advance()? {
+        let record = reader.read_record_owned()?;
+        let event_id = record.get().sampling_event_identifier;
+
+        match by_event.get(event_id) {
+            Some(&index) => checklists[index].push(record),
+            None => {
+                by_event.insert(event_id.to_owned(), checklists.len());
+                checklists.push(vec![record]);
+            }
+        }
+    }
+
+    Ok(checklists
+        .into_iter()
+        .map(|observations| Checklist { observations })
+        .collect())
+}
+
+/// Removes checklists that share a `GROUP IDENTIFIER` with an
+/// already-kept checklist, retaining only the first one seen.
+///
+/// eBird best practice is to de-duplicate shared checklists (where multiple
+/// observers submit the same outing as a group) before analysis, to avoid
+/// double-counting the same observations.
+pub fn dedupe_shared_checklists(checklists: Vec<Checklist>) -> Vec<Checklist> {
+    let mut seen_groups = HashSet::new();
+    checklists
+        .into_iter()
+        .filter(|checklist| match checklist.group_identifier() {
+            Some(group_id) => seen_groups.insert(group_id.to_owned()),
+            None => true,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{ebd_text, sample_row};
+
+    #[test]
+    fn checklist_reader_groups_consecutive_rows_by_sampling_event() {
+        let input = ebd_text(&[
+            sample_row("S1", "", "30.25", "-97.75", "1", "0"),
+            sample_row("S1", "", "30.25", "-97.75", "1", "0"),
+            sample_row("S2", "", "30.25", "-97.75", "1", "0"),
+        ]);
+
+        let mut reader = ChecklistReader::new(Reader::from_reader(input.as_bytes()));
+
+        let first = reader.read_checklist().unwrap().unwrap();
+        assert_eq!(first.sampling_event_identifier(), "S1");
+        assert_eq!(first.observations().len(), 2);
+
+        let second = reader.read_checklist().unwrap().unwrap();
+        assert_eq!(second.sampling_event_identifier(), "S2");
+        assert_eq!(second.observations().len(), 1);
+
+        assert!(reader.read_checklist().unwrap().is_none());
+    }
+
+    #[test]
+    fn group_checklists_buffered_handles_out_of_order_rows() {
+        let input = ebd_text(&[
+            sample_row("S1", "", "30.25", "-97.75", "1", "0"),
+            sample_row("S2", "", "30.25", "-97.75", "1", "0"),
+            sample_row("S1", "", "30.25", "-97.75", "1", "0"),
+        ]);
+
+        let mut reader = Reader::from_reader(input.as_bytes());
+        let checklists = group_checklists_buffered(&mut reader).unwrap();
+
+        assert_eq!(checklists.len(), 2);
+        assert_eq!(checklists[0].sampling_event_identifier(), "S1");
+        assert_eq!(checklists[0].observations().len(), 2);
+        assert_eq!(checklists[1].sampling_event_identifier(), "S2");
+        assert_eq!(checklists[1].observations().len(), 1);
+    }
+
+    #[test]
+    fn dedupe_shared_checklists_keeps_one_per_group() {
+        let input = ebd_text(&[
+            sample_row("S1", "G1", "30.25", "-97.75", "1", "0"),
+            sample_row("S2", "G1", "30.25", "-97.75", "1", "0"),
+            sample_row("S3", "", "30.25", "-97.75", "1", "0"),
+        ]);
+
+        let mut reader = Reader::from_reader(input.as_bytes());
+        let checklists = group_checklists_buffered(&mut reader).unwrap();
+        let deduped = dedupe_shared_checklists(checklists);
+
+        let ids: Vec<&str> = deduped
+            .iter()
+            .map(Checklist::sampling_event_identifier)
+            .collect();
+        assert_eq!(ids, vec!["S1", "S3"]);
+    }
+}
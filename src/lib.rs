@@ -24,6 +24,10 @@
 
 use std::{fmt, io, marker};
 
+pub mod checklist;
+#[cfg(feature = "spatial")]
+pub mod spatial;
+
 pub struct Reader<'a, R: io::Read> {
     csv_reader: csv::Reader<R>,
     csv_byte_record: csv::ByteRecord,
@@ -44,16 +48,254 @@ impl<'a, R: io::Read> Reader<'a, R> {
         }
     }
 
-    pub fn advance<'b>(&'b mut self) -> csv::Result<bool> {
+    pub fn advance(&mut self) -> csv::Result<bool> {
         self.csv_reader.read_byte_record(&mut self.csv_byte_record)
     }
 
     pub fn read_record(&'a self) -> csv::Result<Record<'a>> {
         self.csv_byte_record.deserialize(None)
     }
+
+    /// Like [`Reader::read_record`], but returns a self-referential, owned
+    /// record that does not borrow from `self`.
+    ///
+    /// The returned [`Yoke`] owns a clone of the current row's bytes and can
+    /// be stored in a `Vec`, sorted, or sent across threads, at the cost of
+    /// the clone. For the hot loop, prefer [`Reader::read_record`].
+    pub fn read_record_owned(&self) -> csv::Result<OwnedRecord> {
+        let cart = Box::new(self.csv_byte_record.clone());
+        yoke::Yoke::try_attach_to_cart(cart, |bytes| bytes.deserialize(None))
+    }
+
+    /// Advances past rows whose `(LONGITUDE, LATITUDE)` falls outside the
+    /// given bounding box, without allocating or deserializing a full
+    /// [`Record`] for them.
+    ///
+    /// Otherwise behaves like [`Reader::advance`]: call [`Reader::read_record`]
+    /// or [`Reader::read_record_owned`] afterwards to read the matching row,
+    /// and expect `Ok(false)` at end of input.
+    pub fn advance_within_bbox(
+        &mut self,
+        min_lng: f64,
+        min_lat: f64,
+        max_lng: f64,
+        max_lat: f64,
+    ) -> csv::Result<bool> {
+        let headers = self.csv_reader.byte_headers()?.clone();
+
+        while self.advance()? {
+            let LngLat {
+                longitude,
+                latitude,
+            } = self.csv_byte_record.deserialize(Some(&headers))?;
+            if longitude >= min_lng
+                && longitude <= max_lng
+                && latitude >= min_lat
+                && latitude <= max_lat
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct LngLat {
+    #[serde(rename = "LATITUDE")]
+    latitude: f64,
+
+    #[serde(rename = "LONGITUDE")]
+    longitude: f64,
+}
+
+pub struct Writer<W: io::Write> {
+    csv_writer: csv::Writer<W>,
+}
+
+impl<W: io::Write> Writer<W> {
+    pub fn from_writer(writer: W) -> Self {
+        let csv_writer = csv::WriterBuilder::new()
+            .delimiter(b'\t')
+            .from_writer(writer);
+
+        Writer { csv_writer }
+    }
+
+    pub fn write_record(&mut self, record: &Record<'_>) -> csv::Result<()> {
+        self.csv_writer.serialize(record)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.csv_writer.flush()
+    }
+}
+
+/// An owned, collectible [`Record`] produced by [`Reader::read_record_owned`].
+///
+/// This is a [`Yoke`] pairing a `Record<'static>` with the boxed
+/// `csv::ByteRecord` it borrows from, so the pair can be moved and stored
+/// freely. It is boxed because `Yoke`'s cart must implement `StableDeref`,
+/// which `csv::ByteRecord` itself does not.
+pub type OwnedRecord = yoke::Yoke<Record<'static>, Box<csv::ByteRecord>>;
+
+/// Deserializes a string field into `T` via `parse`, for the small
+/// fixed-vocabulary enums below that fall back to an `Other(String)`
+/// variant rather than erroring on an unrecognized value.
+fn deserialize_str_enum<'de, D, T>(
+    deserializer: D,
+    expecting: &'static str,
+    parse: fn(&str) -> T,
+) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct StrEnumVisitor<T> {
+        expecting: &'static str,
+        parse: fn(&str) -> T,
+    }
+
+    impl<'de, T> serde::de::Visitor<'de> for StrEnumVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str(self.expecting)
+        }
+
+        fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok((self.parse)(s))
+        }
+    }
+
+    deserializer.deserialize_str(StrEnumVisitor { expecting, parse })
+}
+
+/// The value of [`Record::category`], i.e. eBird's taxonomic category for a
+/// reported observation.
+///
+/// `Other` is a catch-all for values not yet known to this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Category {
+    Species,
+    Issf,
+    Slash,
+    Spuh,
+    Hybrid,
+    Intergrade,
+    Domestic,
+    Form,
+    Other(String),
+}
+
+impl<'de> serde::Deserialize<'de> for Category {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_str_enum(deserializer, "an eBird taxonomic category", |s| match s {
+            "species" => Category::Species,
+            "issf" => Category::Issf,
+            "slash" => Category::Slash,
+            "spuh" => Category::Spuh,
+            "hybrid" => Category::Hybrid,
+            "intergrade" => Category::Intergrade,
+            "domestic" => Category::Domestic,
+            "form" => Category::Form,
+            other => Category::Other(other.to_owned()),
+        })
+    }
+}
+
+/// The value of [`Record::protocol_type`], i.e. the eBird checklist protocol
+/// used to collect an observation.
+///
+/// `Other` is a catch-all for values not yet known to this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtocolType {
+    Traveling,
+    Stationary,
+    Incidental,
+    Historical,
+    Other(String),
 }
 
-#[derive(Debug, serde::Deserialize)]
+impl<'de> serde::Deserialize<'de> for ProtocolType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_str_enum(deserializer, "an eBird protocol type", |s| match s {
+            "eBird - Traveling Count" => ProtocolType::Traveling,
+            "eBird - Stationary Count" => ProtocolType::Stationary,
+            "eBird - Incidental Observation" => ProtocolType::Incidental,
+            "eBird - Historical Observation" => ProtocolType::Historical,
+            other => ProtocolType::Other(other.to_owned()),
+        })
+    }
+}
+
+/// The value of [`Record::locality_type`], i.e. what kind of place an
+/// observation's locality is.
+///
+/// `Other` is a catch-all for values not yet known to this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocalityType {
+    Hotspot,
+    Personal,
+    Traveling,
+    Other(String),
+}
+
+impl<'de> serde::Deserialize<'de> for LocalityType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_str_enum(deserializer, "an eBird locality type", |s| match s {
+            "H" => LocalityType::Hotspot,
+            "P" => LocalityType::Personal,
+            "T" => LocalityType::Traveling,
+            other => LocalityType::Other(other.to_owned()),
+        })
+    }
+}
+
+/// The value of [`Record::breeding_bird_atlas_category`], i.e. the breeding
+/// evidence strength inferred from a [`Record::breeding_bird_atlas_code`].
+///
+/// `Other` is a catch-all for values not yet known to this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BreedingCategory {
+    Observed,
+    Possible,
+    Probable,
+    Confirmed,
+    Other(String),
+}
+
+impl<'de> serde::Deserialize<'de> for BreedingCategory {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_str_enum(
+            deserializer,
+            "a breeding bird atlas category",
+            |s| match s {
+                "C1" => BreedingCategory::Observed,
+                "C2" => BreedingCategory::Possible,
+                "C3" => BreedingCategory::Probable,
+                "C4" => BreedingCategory::Confirmed,
+                other => BreedingCategory::Other(other.to_owned()),
+            },
+        )
+    }
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize, yoke::Yokeable)]
 pub struct Record<'a> {
     #[serde(rename = "GLOBAL UNIQUE IDENTIFIER")]
     pub global_unique_identifier: &'a str,
@@ -171,20 +413,33 @@ pub struct Record<'a> {
 
     #[serde(
         rename = "ALL SPECIES REPORTED",
-        deserialize_with = "deserialize_bool_from_u64"
+        deserialize_with = "deserialize_bool_from_u64",
+        serialize_with = "serialize_bool_as_u64"
     )]
     pub all_species_reported: bool,
 
     #[serde(rename = "GROUP IDENTIFIER")]
     pub group_identifier: &'a str,
 
-    #[serde(rename = "HAS MEDIA", deserialize_with = "deserialize_bool_from_u64")]
+    #[serde(
+        rename = "HAS MEDIA",
+        deserialize_with = "deserialize_bool_from_u64",
+        serialize_with = "serialize_bool_as_u64"
+    )]
     pub has_media: bool,
 
-    #[serde(rename = "APPROVED", deserialize_with = "deserialize_bool_from_u64")]
+    #[serde(
+        rename = "APPROVED",
+        deserialize_with = "deserialize_bool_from_u64",
+        serialize_with = "serialize_bool_as_u64"
+    )]
     pub approved: bool,
 
-    #[serde(rename = "REVIEWED", deserialize_with = "deserialize_bool_from_u64")]
+    #[serde(
+        rename = "REVIEWED",
+        deserialize_with = "deserialize_bool_from_u64",
+        serialize_with = "serialize_bool_as_u64"
+    )]
     pub reviewed: bool,
 
     #[serde(rename = "REASON")]
@@ -197,6 +452,125 @@ pub struct Record<'a> {
     pub species_comments: &'a str,
 }
 
+/// The value of [`Record::observation_count`], which the EBD represents as
+/// either an integer or the literal `X`, meaning "species present but not
+/// counted".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObservationCount {
+    Exact(u32),
+    Present,
+}
+
+impl<'a> Record<'a> {
+    /// Parses [`Record::category`] into a [`Category`].
+    pub fn category_parsed(&self) -> Category {
+        parse_str_infallible(self.category)
+    }
+
+    /// Parses [`Record::protocol_type`] into a [`ProtocolType`].
+    pub fn protocol_type_parsed(&self) -> ProtocolType {
+        parse_str_infallible(self.protocol_type)
+    }
+
+    /// Parses [`Record::locality_type`] into a [`LocalityType`].
+    pub fn locality_type_parsed(&self) -> LocalityType {
+        parse_str_infallible(self.locality_type)
+    }
+
+    /// Parses [`Record::breeding_bird_atlas_category`] into a
+    /// [`BreedingCategory`].
+    pub fn breeding_bird_atlas_category_parsed(&self) -> BreedingCategory {
+        parse_str_infallible(self.breeding_bird_atlas_category)
+    }
+
+    /// Parses [`Record::observation_count`], mapping the `X` sentinel (used
+    /// when a species was present but not counted) to
+    /// [`ObservationCount::Present`].
+    pub fn observation_count_parsed(&self) -> Result<ObservationCount, serde::de::value::Error> {
+        use serde::{de::IntoDeserializer, Deserializer};
+
+        struct ObservationCountVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ObservationCountVisitor {
+            type Value = ObservationCount;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an integer or the literal `X`")
+            }
+
+            // `StrDeserializer::deserialize_any` always calls `visit_str`, so
+            // that's the only variant this visitor needs to implement.
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                if s == "X" {
+                    Ok(ObservationCount::Present)
+                } else {
+                    s.parse()
+                        .map(ObservationCount::Exact)
+                        .map_err(|_| E::invalid_value(serde::de::Unexpected::Str(s), &self))
+                }
+            }
+        }
+
+        let deserializer: serde::de::value::StrDeserializer<serde::de::value::Error> =
+            self.observation_count.into_deserializer();
+        deserializer.deserialize_any(ObservationCountVisitor)
+    }
+
+    /// Parses [`Record::observation_date`] (`YYYY-MM-DD`).
+    pub fn observation_date(&self) -> chrono::ParseResult<chrono::NaiveDate> {
+        chrono::NaiveDate::parse_from_str(self.observation_date, "%Y-%m-%d")
+    }
+
+    /// Parses [`Record::duration_minutes`], treating a blank field as `None`
+    /// rather than an error, since many EBD effort columns are left empty.
+    ///
+    /// A non-empty field that fails to parse is a genuine error, not a blank
+    /// field, so it is surfaced rather than also mapped to `None`.
+    pub fn duration_minutes(&self) -> Result<Option<u32>, std::num::ParseIntError> {
+        parse_optional(self.duration_minutes)
+    }
+
+    /// Parses [`Record::effort_distance_km`], treating a blank field as
+    /// `None` rather than an error, since many EBD effort columns are left
+    /// empty.
+    ///
+    /// A non-empty field that fails to parse is a genuine error, not a blank
+    /// field, so it is surfaced rather than also mapped to `None`.
+    pub fn effort_distance_km(&self) -> Result<Option<f64>, std::num::ParseFloatError> {
+        parse_optional(self.effort_distance_km)
+    }
+
+    /// Parses [`Record::number_observers`], treating a blank field as `None`
+    /// rather than an error, since many EBD effort columns are left empty.
+    ///
+    /// A non-empty field that fails to parse is a genuine error, not a blank
+    /// field, so it is surfaced rather than also mapped to `None`.
+    pub fn number_observers(&self) -> Result<Option<u32>, std::num::ParseIntError> {
+        parse_optional(self.number_observers)
+    }
+}
+
+/// Parses `s` into `T`, treating an empty string as `None` rather than an
+/// error. A non-empty string that fails to parse is still an error.
+fn parse_optional<T: std::str::FromStr>(s: &str) -> Result<Option<T>, T::Err> {
+    if s.is_empty() {
+        Ok(None)
+    } else {
+        s.parse().map(Some)
+    }
+}
+
+/// Deserializes `s` into `T` via `T`'s [`serde::Deserialize`] impl, for types
+/// (like the `Other(String)`-catch-all enums in this crate) whose
+/// deserialization cannot fail.
+fn parse_str_infallible<'a, T: serde::Deserialize<'a>>(s: &'a str) -> T {
+    T::deserialize(serde::de::value::StrDeserializer::<serde::de::value::Error>::new(s))
+        .expect("deserialization of a catch-all enum is infallible")
+}
+
 pub fn deserialize_bool_from_u64<'de, D>(deserializer: D) -> Result<bool, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -225,3 +599,190 @@ where
     deserializer.deserialize_any(U64ToBoolVisitor)
 }
 
+/// Serializes as `0`/`1`, the inverse of [`deserialize_bool_from_u64`], so
+/// that writing a [`Record`] with [`Writer`] round-trips through this
+/// crate's own [`Reader`].
+pub fn serialize_bool_as_u64<S>(value: &bool, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_u64(u64::from(*value))
+}
+
+/// Builds minimal, valid EBD text for tests, shared by this module's own
+/// tests and [`checklist`]'s.
+#[cfg(test)]
+pub(crate) mod testing {
+    const HEADER: &[&str] = &[
+        "GLOBAL UNIQUE IDENTIFIER",
+        "LAST EDITED DATE",
+        "TAXONOMIC ORDER",
+        "CATEGORY",
+        "COMMON NAME",
+        "SCIENTIFIC NAME",
+        "SUBSPECIES COMMON NAME",
+        "SUBSPECIES SCIENTIFIC NAME",
+        "OBSERVATION COUNT",
+        "BREEDING BIRD ATLAS CODE",
+        "BREEDING BIRD ATLAS CATEGORY",
+        "AGE/SEX",
+        "COUNTRY",
+        "COUNTRY CODE",
+        "STATE",
+        "STATE CODE",
+        "COUNTY",
+        "COUNTY CODE",
+        "IBA CODE",
+        "BCR CODE",
+        "USFWS CODE",
+        "ATLAS BLOCK",
+        "LOCALITY",
+        "LOCALITY ID",
+        "LOCALITY TYPE",
+        "LATITUDE",
+        "LONGITUDE",
+        "OBSERVATION DATE",
+        "TIME OBSERVATIONS STARTED",
+        "OBSERVER ID",
+        "SAMPLING EVENT IDENTIFIER",
+        "PROTOCOL TYPE",
+        "PROTOCOL CODE",
+        "PROJECT CODE",
+        "DURATION MINUTES",
+        "EFFORT DISTANCE KM",
+        "EFFORT AREA HA",
+        "NUMBER OBSERVERS",
+        "ALL SPECIES REPORTED",
+        "GROUP IDENTIFIER",
+        "HAS MEDIA",
+        "APPROVED",
+        "REVIEWED",
+        "REASON",
+        "TRIP COMMENTS",
+        "SPECIES COMMENTS",
+    ];
+
+    /// A single EBD row, with the handful of fields tests vary as
+    /// parameters and the rest filled in with unremarkable placeholders.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn sample_row<'a>(
+        sampling_event_identifier: &'a str,
+        group_identifier: &'a str,
+        latitude: &'a str,
+        longitude: &'a str,
+        all_species_reported: &'a str,
+        has_media: &'a str,
+    ) -> Vec<&'a str> {
+        vec![
+            "URN:CornellLabOfOrnithology:EBIRD:OBS123",
+            "2021-01-01 00:00:00",
+            "1",
+            "species",
+            "Mallard",
+            "Anas platyrhynchos",
+            "",
+            "",
+            "5",
+            "",
+            "",
+            "",
+            "United States",
+            "US",
+            "Texas",
+            "US-TX",
+            "Travis",
+            "US-TX-453",
+            "",
+            "",
+            "",
+            "",
+            "Lady Bird Lake",
+            "L123456",
+            "H",
+            latitude,
+            longitude,
+            "2021-01-01",
+            "08:00:00",
+            "obsr123",
+            sampling_event_identifier,
+            "eBird - Traveling Count",
+            "P22",
+            "",
+            "60",
+            "1.5",
+            "",
+            "2",
+            all_species_reported,
+            group_identifier,
+            has_media,
+            "1",
+            "1",
+            "",
+            "",
+            "",
+        ]
+    }
+
+    pub(crate) fn ebd_text(rows: &[Vec<&str>]) -> String {
+        let mut text = HEADER.join("\t");
+        text.push('\n');
+        for row in rows {
+            text.push_str(&row.join("\t"));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{testing::*, *};
+
+    #[test]
+    fn writer_round_trips_bool_columns_as_0_1() {
+        let input = ebd_text(&[sample_row("S1", "", "30.25", "-97.75", "1", "0")]);
+
+        let mut reader = Reader::from_reader(input.as_bytes());
+        assert!(reader.advance().unwrap());
+        let record = reader.read_record().unwrap();
+        assert!(record.all_species_reported);
+        assert!(!record.has_media);
+
+        let mut output = Vec::new();
+        {
+            let mut writer = Writer::from_writer(&mut output);
+            writer.write_record(&record).unwrap();
+            writer.flush().unwrap();
+        }
+
+        // The crate's own Reader must be able to read back what Writer wrote.
+        let mut round_tripped = Reader::from_reader(output.as_slice());
+        assert!(round_tripped.advance().unwrap());
+        let round_tripped_record = round_tripped.read_record().unwrap();
+        assert_eq!(
+            round_tripped_record.all_species_reported,
+            record.all_species_reported
+        );
+        assert_eq!(round_tripped_record.has_media, record.has_media);
+    }
+
+    #[test]
+    fn advance_within_bbox_skips_rows_outside_the_box() {
+        let input = ebd_text(&[
+            sample_row("S1", "", "30.25", "-97.75", "1", "0"), // inside
+            sample_row("S2", "", "45.0", "-120.0", "1", "0"),  // outside
+        ]);
+
+        let mut reader = Reader::from_reader(input.as_bytes());
+
+        assert!(reader
+            .advance_within_bbox(-98.0, 30.0, -97.0, 31.0)
+            .unwrap());
+        let record = reader.read_record().unwrap();
+        assert_eq!(record.sampling_event_identifier, "S1");
+
+        assert!(!reader
+            .advance_within_bbox(-98.0, 30.0, -97.0, 31.0)
+            .unwrap());
+    }
+}
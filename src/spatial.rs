@@ -0,0 +1,202 @@
+//! An optional spatial query layer over [`OwnedRecord`]s.
+//!
+//! Requires the `spatial` feature, which pulls in an R-tree via the
+//! [`rstar`] crate. For a cheap streaming alternative that doesn't build an
+//! index, see [`crate::Reader::advance_within_bbox`].
+
+use crate::{OwnedRecord, Record};
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+const KM_PER_DEGREE_LATITUDE: f64 = 110.574;
+
+/// An R-tree over a collection of [`OwnedRecord`]s, keyed by each record's
+/// `(LONGITUDE, LATITUDE)`.
+pub struct SpatialIndex {
+    tree: rstar::RTree<IndexedRecord>,
+}
+
+impl SpatialIndex {
+    /// Builds an index over `records`.
+    pub fn new(records: impl IntoIterator<Item = OwnedRecord>) -> Self {
+        SpatialIndex {
+            tree: rstar::RTree::bulk_load(records.into_iter().map(IndexedRecord).collect()),
+        }
+    }
+
+    /// Returns every record whose `(LONGITUDE, LATITUDE)` falls within the
+    /// given bounding box.
+    pub fn query_bbox(
+        &self,
+        min_lng: f64,
+        min_lat: f64,
+        max_lng: f64,
+        max_lat: f64,
+    ) -> Vec<&Record<'_>> {
+        let envelope = rstar::AABB::from_corners([min_lng, min_lat], [max_lng, max_lat]);
+        self.tree
+            .locate_in_envelope(&envelope)
+            .map(|indexed| indexed.0.get())
+            .collect()
+    }
+
+    /// Returns every record within `radius_km` kilometers of `center`
+    /// (`(longitude, latitude)`).
+    ///
+    /// This first narrows the search with [`SpatialIndex::query_bbox`], then
+    /// filters exactly by great-circle (haversine) distance. The pre-filter
+    /// box wraps around the antimeridian rather than clipping at ±180°, so a
+    /// `center` near the date line still finds nearby records on its far
+    /// side.
+    pub fn query_radius(&self, center: (f64, f64), radius_km: f64) -> Vec<&Record<'_>> {
+        let (center_lng, center_lat) = center;
+        let lat_margin = radius_km / KM_PER_DEGREE_LATITUDE;
+        // The circle's widest longitude extent on the sphere; once the circle
+        // covers a pole, it reaches every longitude.
+        let sin_ratio =
+            (radius_km / EARTH_RADIUS_KM).sin() / center_lat.to_radians().cos().abs();
+        let lng_margin = if center_lat.abs() + lat_margin >= 90.0 || !(sin_ratio < 1.0) {
+            180.0
+        } else {
+            sin_ratio.asin().to_degrees()
+        };
+
+        self.query_bbox_wrapping_antimeridian(
+            center_lng - lng_margin,
+            center_lat - lat_margin,
+            center_lng + lng_margin,
+            center_lat + lat_margin,
+        )
+        .into_iter()
+        .filter(|record| {
+            haversine_km(center_lat, center_lng, record.latitude, record.longitude) <= radius_km
+        })
+        .collect()
+    }
+
+    /// Like [`SpatialIndex::query_bbox`], but `min_lng`/`max_lng` may fall
+    /// outside `[-180, 180]`, as happens when a [`SpatialIndex::query_radius`]
+    /// box is centered near the antimeridian. Any portion outside that range
+    /// is wrapped around the globe and queried separately.
+    fn query_bbox_wrapping_antimeridian(
+        &self,
+        min_lng: f64,
+        min_lat: f64,
+        max_lng: f64,
+        max_lat: f64,
+    ) -> Vec<&Record<'_>> {
+        // A margin this wide already spans the whole globe; querying the
+        // full range directly avoids the wrapped sub-queries below
+        // overlapping the primary one and double-counting records.
+        if max_lng - min_lng >= 360.0 {
+            return self.query_bbox(-180.0, min_lat, 180.0, max_lat);
+        }
+
+        let mut records =
+            self.query_bbox(min_lng.max(-180.0), min_lat, max_lng.min(180.0), max_lat);
+        if min_lng < -180.0 {
+            records.extend(self.query_bbox(min_lng + 360.0, min_lat, 180.0, max_lat));
+        }
+        if max_lng > 180.0 {
+            records.extend(self.query_bbox(-180.0, min_lat, max_lng - 360.0, max_lat));
+        }
+        records
+    }
+}
+
+struct IndexedRecord(OwnedRecord);
+
+impl rstar::RTreeObject for IndexedRecord {
+    type Envelope = rstar::AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        let record = self.0.get();
+        rstar::AABB::from_point([record.longitude, record.latitude])
+    }
+}
+
+impl rstar::PointDistance for IndexedRecord {
+    fn distance_2(&self, point: &[f64; 2]) -> f64 {
+        let record = self.0.get();
+        let dx = record.longitude - point[0];
+        let dy = record.latitude - point[1];
+        dx * dx + dy * dy
+    }
+}
+
+fn haversine_km(lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> f64 {
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lng = (lng2 - lng1).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2)
+        + lat1.to_radians().cos() * lat2.to_radians().cos() * (d_lng / 2.0).sin().powi(2);
+    let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
+    EARTH_RADIUS_KM * c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::{ebd_text, sample_row},
+        Reader,
+    };
+
+    fn index(rows: &[Vec<&str>]) -> SpatialIndex {
+        let input = ebd_text(rows);
+        let mut reader = Reader::from_reader(input.as_bytes());
+        let mut records = Vec::new();
+        while reader.advance().unwrap() {
+            records.push(reader.read_record_owned().unwrap());
+        }
+        SpatialIndex::new(records)
+    }
+
+    #[test]
+    fn query_bbox_includes_records_inside_and_excludes_records_outside() {
+        let index = index(&[
+            sample_row("S1", "", "30.25", "-97.75", "1", "0"), // inside
+            sample_row("S2", "", "45.0", "-120.0", "1", "0"),  // outside
+        ]);
+
+        let found = index.query_bbox(-98.0, 30.0, -97.0, 31.0);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].sampling_event_identifier, "S1");
+    }
+
+    #[test]
+    fn query_radius_includes_nearby_and_excludes_distant_records() {
+        let index = index(&[
+            sample_row("S1", "", "30.26", "-97.76", "1", "0"), // ~1.5km from center
+            sample_row("S2", "", "45.0", "-120.0", "1", "0"),  // far away
+        ]);
+
+        let found = index.query_radius((-97.75, 30.25), 5.0);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].sampling_event_identifier, "S1");
+    }
+
+    #[test]
+    fn query_radius_wraps_around_the_antimeridian() {
+        let index = index(&[
+            sample_row("S1", "", "0.0", "179.9", "1", "0"), // just west of the date line
+            sample_row("S2", "", "0.0", "-179.9", "1", "0"), // just east of the date line
+        ]);
+
+        // Centered right on the antimeridian, a radius covering both of the
+        // points above on the globe requires wrapping the query box.
+        let found = index.query_radius((180.0, 0.0), 50.0);
+        let mut ids: Vec<&str> = found.iter().map(|r| r.sampling_event_identifier).collect();
+        ids.sort_unstable();
+        assert_eq!(ids, vec!["S1", "S2"]);
+    }
+
+    #[test]
+    fn query_radius_does_not_double_count_near_the_poles() {
+        // Near a pole, a single degree of longitude spans very little
+        // distance, so even a modest radius produces a longitude margin
+        // wide enough to wrap all the way around the globe.
+        let index = index(&[sample_row("S1", "", "89.0", "0.0", "1", "0")]);
+
+        let found = index.query_radius((0.0, 89.0), 500.0);
+        assert_eq!(found.len(), 1);
+    }
+}